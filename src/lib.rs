@@ -2,12 +2,11 @@ use indicatif::ProgressBar;
 use itertools::Itertools;
 use sha2::{Digest, Sha256};
 use std::{
-    collections::HashMap,
-    env,
-    error::Error,
-    fmt, fs,
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    env, fmt, fs,
     hash::Hash,
-    io::{self, Read, Write},
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 use text_colorizer::Colorize;
@@ -20,24 +19,47 @@ pub trait FileSystem {
     fn move_file(&mut self, from: &Path, to: &Path) -> std::io::Result<()>;
     fn copy_file(&mut self, from: &Path, to: &Path) -> std::io::Result<()>;
     fn delete_file(&mut self, path: &Path) -> std::io::Result<()>;
+    fn create_dir_all(&mut self, path: &Path) -> std::io::Result<()>;
     fn hash_file(&self, path: &Path) -> std::io::Result<String>;
+    /// Hashes only the first `len` bytes of the file (or all of it, if shorter than `len`).
+    fn hash_file_partial(&self, path: &Path, len: u64) -> std::io::Result<String>;
     fn size(&self, path: &Path) -> std::io::Result<u64>;
+    /// Last-modified time, as nanoseconds since the Unix epoch - coarser resolution would let
+    /// two distinct writes to a same-size file within the same second share a cache key.
+    fn mtime(&self, path: &Path) -> std::io::Result<u64>;
+    fn read_range(&self, path: &Path, offset: u64, len: u64) -> std::io::Result<Vec<u8>>;
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> std::io::Result<()>;
 }
 
 pub struct RealFileSystem;
 
+impl RealFileSystem {
+    /// Walks `path` depth-first, yielding every regular file found in it or any subdirectory.
+    fn walk(path: &Path) -> Box<dyn Iterator<Item = PathBuf>> {
+        let entries: Vec<_> = fs::read_dir(path)
+            .into_iter()
+            .flat_map(|it| it.filter_map(Result::ok))
+            .collect();
+
+        let mut files = Vec::new();
+        for entry in entries {
+            let entry_path = entry.path();
+            match entry.file_type() {
+                Ok(ft) if ft.is_dir() => files.extend(Self::walk(&entry_path)),
+                Ok(ft) if ft.is_file() => files.push(entry_path),
+                _ => {}
+            }
+        }
+        Box::new(files.into_iter())
+    }
+}
+
 impl FileSystem for RealFileSystem {
     fn list_files<'a>(
         &'a mut self,
         path: &'a Path,
     ) -> Box<dyn Iterator<Item = std::path::PathBuf> + 'a> {
-        Box::new(
-            fs::read_dir(path)
-                .into_iter()
-                .flat_map(|it| it.filter_map(Result::ok))
-                .filter(|e| e.file_type().map(|ft| ft.is_file()).unwrap_or(false))
-                .map(|e| e.path()),
-        )
+        Self::walk(path)
     }
 
     fn move_file(&mut self, from: &Path, to: &Path) -> std::io::Result<()> {
@@ -50,6 +72,9 @@ impl FileSystem for RealFileSystem {
     fn delete_file(&mut self, path: &Path) -> std::io::Result<()> {
         fs::remove_file(path)
     }
+    fn create_dir_all(&mut self, path: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(path)
+    }
     fn hash_file(&self, path: &Path) -> std::io::Result<String> {
         let file = fs::File::open(path)?;
         let mut reader = io::BufReader::new(file);
@@ -65,15 +90,57 @@ impl FileSystem for RealFileSystem {
         }
         Ok(format!("{:x}", hasher.finalize()))
     }
+    fn hash_file_partial(&self, path: &Path, len: u64) -> std::io::Result<String> {
+        let file = fs::File::open(path)?;
+        let mut reader = io::BufReader::new(file).take(len);
+        let mut hasher = Sha256::new();
+        let mut buffer = [0u8; 8192]; // 8KB
+
+        loop {
+            let count = reader.read(&mut buffer)?;
+            if count == 0 {
+                break;
+            }
+            hasher.update(&buffer[..count]);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
     fn size(&self, path: &Path) -> std::io::Result<u64> {
         let metadata = fs::metadata(path)?;
         Ok(metadata.len())
     }
+    fn mtime(&self, path: &Path) -> std::io::Result<u64> {
+        let modified = fs::metadata(path)?.modified()?;
+        Ok(modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0))
+    }
+    fn read_range(&self, path: &Path, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+        use std::io::Seek;
+        let mut file = fs::File::open(path)?;
+        file.seek(io::SeekFrom::Start(offset))?;
+        let mut buffer = vec![0u8; len as usize];
+        file.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        fs::write(path, data)
+    }
 }
 
 pub struct FakeFileSystem {
     pub files: HashMap<PathBuf, String>,
     pub operations: Vec<String>,
+    /// Number of times `hash_file`/`hash_file_partial` actually read file content, rather than
+    /// the caller getting a hit out of `HashCache`. A `Cell` because both methods only borrow
+    /// `&self` - hashing a file isn't otherwise a mutation. Lets tests prove a cache hit skipped
+    /// real work instead of just happening to produce the same op log either way.
+    pub hashes_computed: Cell<u32>,
+    /// Synthetic last-modified time for each file, stamped from `clock` on every write - there's
+    /// no real wall clock to read in a fake filesystem.
+    mtimes: HashMap<PathBuf, u64>,
+    clock: u64,
 }
 
 impl FakeFileSystem {
@@ -81,8 +148,17 @@ impl FakeFileSystem {
         Self {
             files: HashMap::new(),
             operations: Vec::new(),
+            hashes_computed: Cell::new(0),
+            mtimes: HashMap::new(),
+            clock: 0,
         }
     }
+
+    /// Advances the synthetic clock and stamps `path` with the new time.
+    fn touch(&mut self, path: &Path) {
+        self.clock += 1;
+        self.mtimes.insert(path.to_path_buf(), self.clock);
+    }
 }
 impl Default for FakeFileSystem {
     fn default() -> Self {
@@ -108,6 +184,7 @@ impl FileSystem for FakeFileSystem {
     fn move_file(&mut self, from: &Path, to: &Path) -> std::io::Result<()> {
         if let Some(content) = self.files.remove(from) {
             self.files.insert(to.to_path_buf(), content);
+            self.touch(to);
             self.operations
                 .push(format!("move: `{}` -> `{}`", from.display(), to.display()));
             Ok(())
@@ -119,6 +196,7 @@ impl FileSystem for FakeFileSystem {
     fn copy_file(&mut self, from: &Path, to: &Path) -> std::io::Result<()> {
         if let Some(content) = self.files.get(from) {
             self.files.insert(to.to_path_buf(), content.clone());
+            self.touch(to);
             self.operations
                 .push(format!("copy: `{}` -> `{}`", from.display(), to.display()));
             Ok(())
@@ -137,17 +215,38 @@ impl FileSystem for FakeFileSystem {
         }
     }
 
+    fn create_dir_all(&mut self, path: &Path) -> std::io::Result<()> {
+        self.operations
+            .push(format!("mkdir: `{}`", path.display()));
+        Ok(())
+    }
+
     fn hash_file(&self, path: &Path) -> io::Result<String> {
         let content = self
             .files
             .get(path)
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "File not found"))?;
 
+        self.hashes_computed.set(self.hashes_computed.get() + 1);
         let mut hasher = Sha256::new();
         hasher.update(content.as_bytes());
         Ok(format!("{:x}", hasher.finalize()))
     }
 
+    fn hash_file_partial(&self, path: &Path, len: u64) -> io::Result<String> {
+        let content = self
+            .files
+            .get(path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "File not found"))?;
+
+        self.hashes_computed.set(self.hashes_computed.get() + 1);
+        let bytes = content.as_bytes();
+        let end = (len as usize).min(bytes.len());
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes[..end]);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
     fn size(&self, path: &Path) -> std::io::Result<u64> {
         if let Some(content) = self.files.get(path) {
             Ok(content.len().try_into().unwrap())
@@ -155,59 +254,219 @@ impl FileSystem for FakeFileSystem {
             Err(io::Error::new(io::ErrorKind::NotFound, "File not found"))
         }
     }
+
+    fn mtime(&self, path: &Path) -> std::io::Result<u64> {
+        if self.files.contains_key(path) {
+            Ok(self.mtimes.get(path).copied().unwrap_or(0))
+        } else {
+            Err(io::Error::new(io::ErrorKind::NotFound, "File not found"))
+        }
+    }
+
+    fn read_range(&self, path: &Path, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+        let content = self
+            .files
+            .get(path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "File not found"))?;
+        let bytes = content.as_bytes();
+        let start = offset as usize;
+        let end = start + len as usize;
+        bytes
+            .get(start..end)
+            .map(|slice| slice.to_vec())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "range out of bounds"))
+    }
+
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        let content = String::from_utf8(data.to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.files.insert(path.to_path_buf(), content);
+        self.touch(path);
+        self.operations
+            .push(format!("write: `{}`", path.display()));
+        Ok(())
+    }
+}
+
+/// How much of a file to hash when disambiguating two same-sized candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashMode {
+    /// Hash only the first `PARTIAL_HASH_SIZE` bytes - cheap, but can collide.
+    Partial,
+    /// Hash the whole file - expensive, but conclusive.
+    Full,
+}
+
+/// Number of leading bytes read for a [`HashMode::Partial`] hash.
+const PARTIAL_HASH_SIZE: u64 = 4096;
+
+/// Name of the on-disk cache file kept at the root of the destination tree.
+const HASH_CACHE_FILE: &str = ".rs-ync-cache";
+
+/// A cached hash, valid only as long as `size` and `mtime` still match the file on disk.
+#[derive(Debug, Clone, Default)]
+struct CacheEntry {
+    size: u64,
+    mtime: u64,
+    partial_hash: Option<String>,
+    full_hash: Option<String>,
+}
+
+/// On-disk hash cache keyed by path, guarded by `(size, mtime)` - mirrors Mercurial's dirstate:
+/// loaded once at the start of a sync, updated in memory as files get hashed, flushed back out
+/// at the end, so a repeat sync over an unchanged tree can skip re-hashing entirely.
+#[derive(Debug, Default)]
+pub struct HashCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl HashCache {
+    /// Loads the cache file at `path` through `file_sys`, or starts empty if it's missing,
+    /// unreadable, or not UTF-8. Lines that don't parse are skipped; the rest are kept.
+    pub fn load(path: &Path, file_sys: &impl FileSystem) -> Self {
+        let contents = file_sys
+            .size(path)
+            .and_then(|len| file_sys.read_range(path, 0, len))
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok());
+
+        let mut entries = HashMap::new();
+        if let Some(contents) = contents {
+            for line in contents.lines() {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() != 5 {
+                    continue;
+                }
+                let (file_path, size, mtime, partial, full) =
+                    (fields[0], fields[1], fields[2], fields[3], fields[4]);
+                let (Ok(size), Ok(mtime)) = (size.parse(), mtime.parse()) else {
+                    continue;
+                };
+                entries.insert(
+                    PathBuf::from(file_path),
+                    CacheEntry {
+                        size,
+                        mtime,
+                        partial_hash: (!partial.is_empty()).then(|| partial.to_string()),
+                        full_hash: (!full.is_empty()).then(|| full.to_string()),
+                    },
+                );
+            }
+        }
+        HashCache { entries }
+    }
+
+    /// Persists the cache back to `path` through `file_sys`, one line per file, sorted by path
+    /// for a deterministic file (a `HashMap`'s own iteration order isn't).
+    pub fn save(&self, path: &Path, file_sys: &mut impl FileSystem) -> io::Result<()> {
+        let mut contents = String::new();
+        for (file_path, entry) in self.entries.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+            contents.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                file_path.display(),
+                entry.size,
+                entry.mtime,
+                entry.partial_hash.as_deref().unwrap_or(""),
+                entry.full_hash.as_deref().unwrap_or(""),
+            ));
+        }
+        file_sys.write_file(path, contents.as_bytes())
+    }
+
+    fn lookup(&self, path: &Path, size: u64, mtime: u64, mode: HashMode) -> Option<&str> {
+        let entry = self.entries.get(path)?;
+        if entry.size != size || entry.mtime != mtime {
+            return None;
+        }
+        match mode {
+            HashMode::Partial => entry.partial_hash.as_deref(),
+            HashMode::Full => entry.full_hash.as_deref(),
+        }
+    }
+
+    fn record(&mut self, path: &Path, size: u64, mtime: u64, mode: HashMode, hash: String) {
+        let entry = self.entries.entry(path.to_path_buf()).or_default();
+        if entry.size != size || entry.mtime != mtime {
+            *entry = CacheEntry::default();
+        }
+        entry.size = size;
+        entry.mtime = mtime;
+        match mode {
+            HashMode::Partial => entry.partial_hash = Some(hash),
+            HashMode::Full => entry.full_hash = Some(hash),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Blob {
     pub path: PathBuf,
-    pub basename: PathBuf,
-    pub dir: PathBuf,
-    hash: String,
-    pub id: String,
+    /// Path of this file relative to the root of the tree it was listed from.
+    pub rel_path: PathBuf,
+    partial_hash: Option<String>,
+    full_hash: Option<String>,
     size: u64,
 }
 
 impl Blob {
-    fn new(path: &Path, file_sys: &impl FileSystem) -> Result<Blob, io::Error> {
-        let basename: PathBuf = path
-            .file_name()
-            .ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "basename operation failed",
-            ))?
-            .into();
-        let dir: PathBuf = path
-            .parent()
-            .ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "parent operation failed",
-            ))?
-            .to_path_buf();
-
-        let hash = file_sys.hash_file(path)?;
+    fn new(path: &Path, root: &Path, file_sys: &impl FileSystem) -> Result<Blob, io::Error> {
+        let rel_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
         let size = file_sys.size(path)?;
-        let id = format!(
-            "{}-{}-{}",
-            basename.to_string_lossy().into_owned(),
-            hash,
-            size
-        );
+
         Ok(Blob {
             path: path.to_path_buf(),
-            basename,
-            dir,
-            hash,
-            id,
+            rel_path,
+            partial_hash: None,
+            full_hash: None,
             size,
         })
     }
+
+    /// Returns the hash for `mode`, computing and caching it on first use. Two blobs with a
+    /// unique size between them never need this at all - it's only reached once a size
+    /// collision means hashing is the only way left to tell the files apart. Beyond the
+    /// in-memory cache on `self`, a miss is checked against the on-disk `cache` before actually
+    /// re-reading the file, and any freshly computed hash is recorded back into it.
+    fn hash(
+        &mut self,
+        file_sys: &impl FileSystem,
+        mode: HashMode,
+        cache: &mut HashCache,
+    ) -> io::Result<&str> {
+        let cached = match mode {
+            HashMode::Partial => &mut self.partial_hash,
+            HashMode::Full => &mut self.full_hash,
+        };
+        if cached.is_none() {
+            let mtime = file_sys.mtime(&self.path)?;
+            let hash = match cache.lookup(&self.path, self.size, mtime, mode) {
+                Some(hash) => hash.to_string(),
+                None => {
+                    let computed = match mode {
+                        HashMode::Partial => {
+                            file_sys.hash_file_partial(&self.path, PARTIAL_HASH_SIZE)?
+                        }
+                        HashMode::Full => file_sys.hash_file(&self.path)?,
+                    };
+                    cache.record(&self.path, self.size, mtime, mode, computed.clone());
+                    computed
+                }
+            };
+            *cached = Some(hash);
+        }
+        Ok(cached.as_deref().unwrap())
+    }
 }
 
 pub fn paths_to_blobs(
     paths: &[PathBuf],
+    root: &Path,
     file_sys: &mut impl FileSystem,
 ) -> Result<Vec<Blob>, io::Error> {
-    paths.iter().map(|path| Blob::new(path, file_sys)).collect()
+    paths
+        .iter()
+        .map(|path| Blob::new(path, root, file_sys))
+        .collect()
 }
 
 /// Creates a `HashMap` from a collection of items, keyed by a field extracted via `key_fn`.
@@ -225,10 +484,135 @@ where
     map
 }
 
-pub fn get_struct_map(root_dir: &PathBuf, file_sys: &mut impl FileSystem) -> HashMap<String, Blob> {
-    let paths: Vec<PathBuf> = file_sys.list_files(Path::new(root_dir)).collect();
-    let blobs: Vec<Blob> = paths_to_blobs(&paths, file_sys).expect("Failed to parse blobs");
-    struct_to_hashmap(blobs, |s| s.id.clone())
+/// Name of the optional per-source-tree ignore file, read once per sync.
+const RSYNCIGNORE_FILE: &str = ".rsyncignore";
+
+/// Path-based exclude/include filtering, modeled on gitignore/Mercurial matchers: a path is
+/// excluded if it matches any exclude glob, unless an include glob overrides it.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    excludes: Vec<String>,
+    includes: Vec<String>,
+}
+
+impl IgnoreMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_exclude(&mut self, pattern: impl Into<String>) {
+        self.excludes.push(pattern.into());
+    }
+
+    pub fn add_include(&mut self, pattern: impl Into<String>) {
+        self.includes.push(pattern.into());
+    }
+
+    /// Reads `.rsyncignore` from `root` (one glob per line, blank lines and `#` comments
+    /// ignored) and adds each line as an exclude pattern. A missing or unreadable file is
+    /// silently treated as "no extra excludes".
+    pub fn load_rsyncignore(&mut self, root: &Path, file_sys: &impl FileSystem) {
+        let ignore_path = root.join(RSYNCIGNORE_FILE);
+        let contents = file_sys
+            .size(&ignore_path)
+            .and_then(|len| file_sys.read_range(&ignore_path, 0, len))
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok());
+
+        let Some(contents) = contents else {
+            return;
+        };
+        for line in contents.lines() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                self.add_exclude(line);
+            }
+        }
+    }
+
+    /// True if `rel_path` (relative to the sync root) should be left out of the sync.
+    pub fn is_excluded(&self, rel_path: &Path) -> bool {
+        let candidate = rel_path.to_string_lossy().replace('\\', "/");
+        if self.includes.iter().any(|p| glob_match(p, &candidate)) {
+            return false;
+        }
+        self.excludes.iter().any(|p| glob_match(p, &candidate))
+    }
+}
+
+/// Matches `path` against `pattern`. A pattern without a `/` matches any single path segment
+/// (so `*.log` or a bare directory name like `build` match at any depth); a pattern containing
+/// `/` is matched segment-by-segment from the root, where `**` matches zero or more segments
+/// and `*` matches within a single segment.
+fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    if !pattern.contains('/') {
+        return path.split('/').any(|segment| segment_match(pattern, segment));
+    }
+    let pattern_segments: Vec<&str> = pattern.split('/').collect();
+    let path_segments: Vec<&str> = path.split('/').collect();
+    segments_match(&pattern_segments, &path_segments)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(&"**") => {
+            segments_match(&pattern[1..], path)
+                || (!path.is_empty() && segments_match(pattern, &path[1..]))
+        }
+        Some(&seg) => {
+            !path.is_empty()
+                && segment_match(seg, path[0])
+                && segments_match(&pattern[1..], &path[1..])
+        }
+    }
+}
+
+/// Matches one path segment against one pattern segment, where `*` stands for any run of
+/// characters (never crossing a `/`, since segments are already split on it).
+fn segment_match(pattern: &str, segment: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == segment;
+    }
+
+    let mut rest = segment;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i == 0 {
+            if !rest.starts_with(part) {
+                return false;
+            }
+            rest = &rest[part.len()..];
+        } else if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        } else if let Some(pos) = rest.find(part) {
+            rest = &rest[pos + part.len()..];
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn get_struct_map(
+    root_dir: &PathBuf,
+    file_sys: &mut impl FileSystem,
+    matcher: &IgnoreMatcher,
+) -> HashMap<String, Blob> {
+    let paths: Vec<PathBuf> = file_sys
+        .list_files(Path::new(root_dir))
+        .filter(|path| path.file_name().and_then(|name| name.to_str()) != Some(HASH_CACHE_FILE))
+        .collect();
+    let blobs: Vec<Blob> =
+        paths_to_blobs(&paths, root_dir, file_sys).expect("Failed to parse blobs");
+    let blobs = blobs
+        .into_iter()
+        .filter(|blob| !matcher.is_excluded(&blob.rel_path));
+    struct_to_hashmap(blobs, |s| s.rel_path.to_string_lossy().into_owned())
 }
 
 #[derive(Debug)]
@@ -237,42 +621,394 @@ pub enum FileOp {
         src_path: PathBuf,
         dst_path: PathBuf,
     },
+    MoveFile {
+        src_path: PathBuf,
+        dst_path: PathBuf,
+    },
+    PatchFile {
+        src_path: PathBuf,
+        dst_path: PathBuf,
+    },
     DeleteFile {
         path: PathBuf,
     },
 }
 
+/// Block size used by the rsync-style block delta algorithm, in bytes.
+const DELTA_BLOCK_SIZE: usize = 4096;
+/// Modulus for the rolling weak checksum, matching the original rsync algorithm (2^16).
+const WEAK_CHECKSUM_MODULUS: u32 = 1 << 16;
+
+/// Rolling weak checksum over a sliding window, as used by the rsync algorithm: `a` is the
+/// sum of the window's bytes, `b` is the weighted sum, both mod `WEAK_CHECKSUM_MODULUS`.
+#[derive(Debug, Clone, Copy)]
+struct WeakChecksum {
+    a: u32,
+    b: u32,
+}
+
+impl WeakChecksum {
+    fn new(block: &[u8]) -> Self {
+        let len = block.len() as u32;
+        let mut a: u32 = 0;
+        let mut b: u32 = 0;
+        for (i, &byte) in block.iter().enumerate() {
+            a = a.wrapping_add(byte as u32);
+            b = b.wrapping_add((len - i as u32) * byte as u32);
+        }
+        WeakChecksum {
+            a: a % WEAK_CHECKSUM_MODULUS,
+            b: b % WEAK_CHECKSUM_MODULUS,
+        }
+    }
+
+    fn sum(&self) -> u64 {
+        self.a as u64 + ((self.b as u64) << 16)
+    }
+
+    /// Slides the window one byte to the right in O(1): `out_byte` leaves at the front,
+    /// `in_byte` enters at the back, `window_len` is the (constant) window size.
+    fn roll(&mut self, out_byte: u8, in_byte: u8, window_len: usize) {
+        let m = WEAK_CHECKSUM_MODULUS as i64;
+        let a = (self.a as i64 - out_byte as i64 + in_byte as i64).rem_euclid(m);
+        let b = (self.b as i64 - (window_len as i64) * (out_byte as i64) + a).rem_euclid(m);
+        self.a = a as u32;
+        self.b = b as u32;
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// A destination block's signature: where it lives, how big it is, and its strong checksum.
+struct BlockSignature {
+    offset: u64,
+    len: usize,
+    strong: String,
+}
+
+/// Splits `data` into non-overlapping `DELTA_BLOCK_SIZE` blocks and indexes each by its weak
+/// checksum, so a source scan can look up "does any destination block look like this?" in O(1).
+fn compute_block_signatures(data: &[u8]) -> HashMap<u64, Vec<BlockSignature>> {
+    let mut signatures: HashMap<u64, Vec<BlockSignature>> = HashMap::new();
+    let mut offset = 0usize;
+    while offset < data.len() {
+        let block_len = DELTA_BLOCK_SIZE.min(data.len() - offset);
+        let block = &data[offset..offset + block_len];
+        let weak = WeakChecksum::new(block).sum();
+        signatures.entry(weak).or_default().push(BlockSignature {
+            offset: offset as u64,
+            len: block_len,
+            strong: sha256_hex(block),
+        });
+        offset += block_len;
+    }
+    signatures
+}
+
+#[derive(Debug, PartialEq)]
+enum DeltaInstruction {
+    /// Reuse `len` bytes already present at `offset` in the destination file.
+    CopyBlock { offset: u64, len: usize },
+    /// Bytes that don't match any destination block and must be sent as-is.
+    Literal(Vec<u8>),
+}
+
+/// Scans `source` against `dst_signatures`, rolling the weak checksum byte-by-byte, and emits
+/// an instruction stream that reconstructs `source` from destination blocks plus literal bytes.
+fn compute_delta(
+    source: &[u8],
+    dst_signatures: &HashMap<u64, Vec<BlockSignature>>,
+) -> Vec<DeltaInstruction> {
+    let mut instructions = Vec::new();
+    let mut literal_buf: Vec<u8> = Vec::new();
+    let len = source.len();
+    if len == 0 {
+        return instructions;
+    }
+
+    let mut i = 0;
+    let mut block_len = DELTA_BLOCK_SIZE.min(len - i);
+    let mut weak = WeakChecksum::new(&source[i..i + block_len]);
+
+    loop {
+        let window = &source[i..i + block_len];
+        let matched = dst_signatures.get(&weak.sum()).and_then(|candidates| {
+            candidates
+                .iter()
+                .find(|sig| sig.len == block_len && sig.strong == sha256_hex(window))
+        });
+
+        let jumped = match matched {
+            Some(sig) => {
+                if !literal_buf.is_empty() {
+                    instructions.push(DeltaInstruction::Literal(std::mem::take(
+                        &mut literal_buf,
+                    )));
+                }
+                instructions.push(DeltaInstruction::CopyBlock {
+                    offset: sig.offset,
+                    len: sig.len,
+                });
+                i += block_len;
+                true
+            }
+            None => {
+                literal_buf.push(source[i]);
+                i += 1;
+                false
+            }
+        };
+
+        if i >= len {
+            break;
+        }
+
+        let next_block_len = DELTA_BLOCK_SIZE.min(len - i);
+        if !jumped && next_block_len == block_len {
+            let out_byte = source[i - 1];
+            let in_byte = source[i + block_len - 1];
+            weak.roll(out_byte, in_byte, block_len);
+        } else {
+            block_len = next_block_len;
+            weak = WeakChecksum::new(&source[i..i + block_len]);
+        }
+    }
+
+    if !literal_buf.is_empty() {
+        instructions.push(DeltaInstruction::Literal(literal_buf));
+    }
+    instructions
+}
+
+/// Rebuilds a file's bytes from a `compute_delta` instruction stream and the destination's
+/// current contents (the source for any `CopyBlock` instruction).
+fn apply_delta(instructions: &[DeltaInstruction], dst_data: &[u8]) -> Vec<u8> {
+    let mut result = Vec::new();
+    for instruction in instructions {
+        match instruction {
+            DeltaInstruction::CopyBlock { offset, len } => {
+                let start = *offset as usize;
+                result.extend_from_slice(&dst_data[start..start + len]);
+            }
+            DeltaInstruction::Literal(bytes) => result.extend_from_slice(bytes),
+        }
+    }
+    result
+}
+
+/// Patches `dst_path` to match `src_path` by transferring only the blocks that changed,
+/// using the rsync rolling-checksum algorithm instead of re-copying the whole file.
+fn patch_file(
+    file_sys: &mut impl FileSystem,
+    src_path: &Path,
+    dst_path: &Path,
+) -> io::Result<()> {
+    let dst_data = file_sys.read_range(dst_path, 0, file_sys.size(dst_path)?)?;
+    let src_data = file_sys.read_range(src_path, 0, file_sys.size(src_path)?)?;
+
+    let signatures = compute_block_signatures(&dst_data);
+    let instructions = compute_delta(&src_data, &signatures);
+    let patched = apply_delta(&instructions, &dst_data);
+
+    file_sys.write_file(dst_path, &patched)
+}
+
+/// Compares two blobs by content, escalating from size to a partial hash to a full hash only
+/// as far as each cheaper check leaves ambiguous - a size mismatch never needs a hash at all.
+fn blobs_match(
+    src: &mut Blob,
+    dst: &mut Blob,
+    file_sys: &impl FileSystem,
+    cache: &mut HashCache,
+) -> io::Result<bool> {
+    if src.size != dst.size {
+        return Ok(false);
+    }
+    if src.hash(file_sys, HashMode::Partial, cache)? != dst.hash(file_sys, HashMode::Partial, cache)? {
+        return Ok(false);
+    }
+    Ok(src.hash(file_sys, HashMode::Full, cache)? == dst.hash(file_sys, HashMode::Full, cache)?)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CandidateSide {
+    Copy,
+    Delete,
+}
+
+/// Finds `(to_copy index, to_delete index)` pairs whose content is identical, so a rename can
+/// be reported as a move instead of a redundant copy + delete. Mirrors a duplicate-file
+/// scanner: group candidates by size first (a unique size needs no hashing at all, since
+/// there's nothing else it could match), then only hash within a colliding size group,
+/// escalating from a partial to a full hash only when the partial hash still collides.
+fn find_move_pairs(
+    to_copy: &mut [Blob],
+    to_delete: &mut [Blob],
+    file_sys: &impl FileSystem,
+    cache: &mut HashCache,
+) -> Vec<(usize, usize)> {
+    let mut by_size: HashMap<u64, Vec<(CandidateSide, usize)>> = HashMap::new();
+    for (i, blob) in to_copy.iter().enumerate() {
+        by_size
+            .entry(blob.size)
+            .or_default()
+            .push((CandidateSide::Copy, i));
+    }
+    for (i, blob) in to_delete.iter().enumerate() {
+        by_size
+            .entry(blob.size)
+            .or_default()
+            .push((CandidateSide::Delete, i));
+    }
+
+    let mut pairs = Vec::new();
+    for candidates in by_size.into_values() {
+        if candidates.len() < 2 {
+            continue;
+        }
+        for group in group_by_hash(
+            candidates,
+            to_copy,
+            to_delete,
+            file_sys,
+            HashMode::Partial,
+            cache,
+        ) {
+            pair_up(group, &mut pairs);
+        }
+    }
+    pairs
+}
+
+/// Splits same-size `candidates` into content-identical groups, hashing at `mode` and - only
+/// if that still leaves a group ambiguous - recursing once more at `HashMode::Full`.
+fn group_by_hash(
+    candidates: Vec<(CandidateSide, usize)>,
+    to_copy: &mut [Blob],
+    to_delete: &mut [Blob],
+    file_sys: &impl FileSystem,
+    mode: HashMode,
+    cache: &mut HashCache,
+) -> Vec<Vec<(CandidateSide, usize)>> {
+    let mut by_hash: HashMap<String, Vec<(CandidateSide, usize)>> = HashMap::new();
+    for (side, i) in candidates {
+        let blob = match side {
+            CandidateSide::Copy => &mut to_copy[i],
+            CandidateSide::Delete => &mut to_delete[i],
+        };
+        let hash = blob
+            .hash(file_sys, mode, cache)
+            .expect("hash operation failed")
+            .to_string();
+        by_hash.entry(hash).or_default().push((side, i));
+    }
+
+    let mut groups = Vec::new();
+    for group in by_hash.into_values() {
+        if group.len() < 2 || mode == HashMode::Full {
+            groups.push(group);
+        } else {
+            groups.extend(group_by_hash(
+                group,
+                to_copy,
+                to_delete,
+                file_sys,
+                HashMode::Full,
+                cache,
+            ));
+        }
+    }
+    groups
+}
+
+/// Greedily pairs up the copy/delete candidates within one content-identical group.
+fn pair_up(group: Vec<(CandidateSide, usize)>, pairs: &mut Vec<(usize, usize)>) {
+    let mut copies = group
+        .iter()
+        .filter(|(side, _)| matches!(side, CandidateSide::Copy))
+        .map(|(_, i)| *i);
+    let mut deletes = group
+        .iter()
+        .filter(|(side, _)| matches!(side, CandidateSide::Delete))
+        .map(|(_, i)| *i);
+    while let (Some(c), Some(d)) = (copies.next(), deletes.next()) {
+        pairs.push((c, d));
+    }
+}
+
 pub fn plan_file_movements(
     dst_dir: &PathBuf,
-    src_map: &HashMap<String, Blob>,
-    dst_map: &HashMap<String, Blob>,
+    src_map: &mut HashMap<String, Blob>,
+    dst_map: &mut HashMap<String, Blob>,
+    file_sys: &impl FileSystem,
+    cache: &mut HashCache,
 ) -> Vec<FileOp> {
-    let mut file_ops = Vec::new();
+    let src_keys: Vec<String> = src_map.keys().cloned().sorted().collect();
+    let dst_keys: Vec<String> = dst_map.keys().cloned().sorted().collect();
 
-    for key in src_map.keys().sorted() {
-        let blob = src_map.get(key).expect("expected key not in src_map");
-        match dst_map.get(key) {
-            Some(_) => {}
-            None => {
-                file_ops.push(FileOp::CopyFile {
-                    src_path: blob.path.clone(),
-                    dst_path: PathBuf::from(dst_dir).join(blob.basename.clone()),
+    let mut to_copy: Vec<Blob> = Vec::new();
+    let mut patch_ops = Vec::new();
+    for key in &src_keys {
+        if dst_map.contains_key(key) {
+            let identical = {
+                let src_blob = src_map.get_mut(key).expect("expected key not in src_map");
+                let dst_blob = dst_map.get_mut(key).expect("expected key not in dst_map");
+                blobs_match(src_blob, dst_blob, file_sys, cache).expect("hash comparison failed")
+            };
+            if !identical {
+                patch_ops.push(FileOp::PatchFile {
+                    src_path: src_map[key].path.clone(),
+                    dst_path: dst_map[key].path.clone(),
                 });
             }
+        } else {
+            to_copy.push(src_map[key].clone());
+        }
+    }
+
+    let mut to_delete: Vec<Blob> = Vec::new();
+    for key in &dst_keys {
+        if !src_map.contains_key(key) {
+            to_delete.push(dst_map[key].clone());
         }
     }
 
-    for key in dst_map.keys().sorted() {
-        let blob = dst_map.get(key).expect("expected key not in dst_map");
-        match src_map.get(key) {
-            Some(_) => {}
+    let pairs = find_move_pairs(&mut to_copy, &mut to_delete, file_sys, cache);
+    let copy_to_delete: HashMap<usize, usize> = pairs.iter().cloned().collect();
+    let moved_delete_idx: HashSet<usize> = pairs.iter().map(|(_, d)| *d).collect();
+
+    let mut file_ops = Vec::new();
+    for (i, blob) in to_copy.iter().enumerate() {
+        match copy_to_delete.get(&i) {
+            Some(&delete_idx) => {
+                file_ops.push(FileOp::MoveFile {
+                    src_path: to_delete[delete_idx].path.clone(),
+                    dst_path: PathBuf::from(dst_dir).join(&blob.rel_path),
+                });
+            }
             None => {
-                file_ops.push(FileOp::DeleteFile {
-                    path: blob.path.clone(),
+                file_ops.push(FileOp::CopyFile {
+                    src_path: blob.path.clone(),
+                    dst_path: PathBuf::from(dst_dir).join(&blob.rel_path),
                 });
             }
         }
     }
+
+    for (i, blob) in to_delete.iter().enumerate() {
+        if !moved_delete_idx.contains(&i) {
+            file_ops.push(FileOp::DeleteFile {
+                path: blob.path.clone(),
+            });
+        }
+    }
+
+    file_ops.extend(patch_ops);
+
     file_ops
 }
 
@@ -283,7 +1019,19 @@ pub fn execute_file_movement_plan(
     let bar = ProgressBar::new(file_plan.len().try_into().unwrap());
     for op in file_plan {
         match op {
-            FileOp::CopyFile { src_path, dst_path } => file_sys.copy_file(&src_path, &dst_path),
+            FileOp::CopyFile { src_path, dst_path } => {
+                if let Some(parent) = dst_path.parent() {
+                    file_sys.create_dir_all(parent).expect("mkdir operation failed");
+                }
+                file_sys.copy_file(&src_path, &dst_path)
+            }
+            FileOp::MoveFile { src_path, dst_path } => {
+                if let Some(parent) = dst_path.parent() {
+                    file_sys.create_dir_all(parent).expect("mkdir operation failed");
+                }
+                file_sys.move_file(&src_path, &dst_path)
+            }
+            FileOp::PatchFile { src_path, dst_path } => patch_file(file_sys, &src_path, &dst_path),
             FileOp::DeleteFile { path } => file_sys.delete_file(&path),
         }
         .expect("{op} operation failed");
@@ -293,13 +1041,209 @@ pub fn execute_file_movement_plan(
     Ok(())
 }
 
+/// The kind of action `plan_file_movements` decided on for one file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    Copy,
+    Move,
+    Patch,
+    Delete,
+}
+
+impl ActionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ActionKind::Copy => "copy",
+            ActionKind::Move => "move",
+            ActionKind::Patch => "patch",
+            ActionKind::Delete => "delete",
+        }
+    }
+}
+
+/// One planned filesystem action, detailed enough to print or serialize on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedAction {
+    pub kind: ActionKind,
+    pub from: Option<PathBuf>,
+    pub path: PathBuf,
+}
+
+impl fmt::Display for PlannedAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.from {
+            Some(from) => write!(
+                f,
+                "{} {} -> {}",
+                self.kind.as_str(),
+                from.display(),
+                self.path.display()
+            ),
+            None => write!(f, "{} {}", self.kind.as_str(), self.path.display()),
+        }
+    }
+}
+
+/// A machine-readable summary of a planned sync - the counts plus each individual action -
+/// so a dry run can be previewed or a script can drive the crate without ever touching the
+/// filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlanSummary {
+    pub copies: usize,
+    pub moves: usize,
+    pub patches: usize,
+    pub deletes: usize,
+    pub actions: Vec<PlannedAction>,
+}
+
+impl PlanSummary {
+    fn from_ops(ops: &[FileOp]) -> Self {
+        let mut summary = PlanSummary::default();
+        for op in ops {
+            let action = match op {
+                FileOp::CopyFile { src_path, dst_path } => {
+                    summary.copies += 1;
+                    PlannedAction {
+                        kind: ActionKind::Copy,
+                        from: Some(src_path.clone()),
+                        path: dst_path.clone(),
+                    }
+                }
+                FileOp::MoveFile { src_path, dst_path } => {
+                    summary.moves += 1;
+                    PlannedAction {
+                        kind: ActionKind::Move,
+                        from: Some(src_path.clone()),
+                        path: dst_path.clone(),
+                    }
+                }
+                FileOp::PatchFile { src_path, dst_path } => {
+                    summary.patches += 1;
+                    PlannedAction {
+                        kind: ActionKind::Patch,
+                        from: Some(src_path.clone()),
+                        path: dst_path.clone(),
+                    }
+                }
+                FileOp::DeleteFile { path } => {
+                    summary.deletes += 1;
+                    PlannedAction {
+                        kind: ActionKind::Delete,
+                        from: None,
+                        path: path.clone(),
+                    }
+                }
+            };
+            summary.actions.push(action);
+        }
+        summary
+    }
+
+    /// Renders the summary as a single JSON object, for scripting against the crate without
+    /// parsing the human-readable `Display` output.
+    pub fn to_json(&self) -> String {
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| {
+                let from = action
+                    .from
+                    .as_ref()
+                    .map(|p| format!("\"{}\"", json_escape(&p.display().to_string())))
+                    .unwrap_or_else(|| "null".to_string());
+                format!(
+                    r#"{{"kind":"{}","from":{},"path":"{}"}}"#,
+                    action.kind.as_str(),
+                    from,
+                    json_escape(&action.path.display().to_string()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#"{{"copies":{},"moves":{},"patches":{},"deletes":{},"actions":[{}]}}"#,
+            self.copies, self.moves, self.patches, self.deletes, actions
+        )
+    }
+}
+
+impl fmt::Display for PlanSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for action in &self.actions {
+            writeln!(f, "{action}")?;
+        }
+        write!(
+            f,
+            "{} copies, {} moves, {} patches, {} deletes",
+            self.copies, self.moves, self.patches, self.deletes
+        )
+    }
+}
+
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Builds the matcher, loads both trees, and computes the file operation plan - everything
+/// `execute_rsync` needs before it decides whether to actually touch the filesystem.
+fn build_plan(
+    args: &Args,
+    file_sys: &mut impl FileSystem,
+) -> (Vec<FileOp>, PathBuf, HashCache) {
+    let mut matcher = IgnoreMatcher::new();
+    for pattern in &args.excludes {
+        matcher.add_exclude(pattern);
+    }
+    for pattern in &args.includes {
+        matcher.add_include(pattern);
+    }
+    matcher.load_rsyncignore(&args.src_dir, file_sys);
+
+    let mut src_map = get_struct_map(&args.src_dir, file_sys, &matcher);
+    let mut dst_map = get_struct_map(&args.dst_dir, file_sys, &matcher);
+
+    let cache_path = args.dst_dir.join(HASH_CACHE_FILE);
+    let mut cache = HashCache::load(&cache_path, file_sys);
+
+    let ops_plan = plan_file_movements(&args.dst_dir, &mut src_map, &mut dst_map, file_sys, &mut cache);
+
+    (ops_plan, cache_path, cache)
+}
+
+/// Computes the sync plan for `args` and returns it as a [`PlanSummary`], without touching the
+/// filesystem - the same plan a `--dry-run` sync would print, exposed for scripting.
+pub fn plan_rsync(args: &Args, file_sys: &mut impl FileSystem) -> PlanSummary {
+    let (ops_plan, _cache_path, _cache) = build_plan(args, file_sys);
+    PlanSummary::from_ops(&ops_plan)
+}
+
 pub fn execute_rsync(args: Args, file_sys: &mut impl FileSystem) -> Result<(), io::Error> {
-    let src_map = get_struct_map(&args.src_dir, file_sys);
-    let dst_map = get_struct_map(&args.dst_dir, file_sys);
+    let (ops_plan, cache_path, cache) = build_plan(&args, file_sys);
 
-    let ops_plan = plan_file_movements(&args.dst_dir, &src_map, &dst_map);
+    if args.dry_run {
+        let summary = PlanSummary::from_ops(&ops_plan);
+        if args.json {
+            print!("{}", summary.to_json());
+        } else {
+            print!("{summary}");
+        }
+        return Ok(());
+    }
+
+    execute_file_movement_plan(file_sys, ops_plan)?;
 
-    execute_file_movement_plan(file_sys, ops_plan)
+    // Best-effort: a failed save just means the next run re-hashes everything.
+    let _ = cache.save(&cache_path, file_sys);
+    Ok(())
 }
 
 // cli stuff
@@ -307,26 +1251,68 @@ pub fn execute_rsync(args: Args, file_sys: &mut impl FileSystem) -> Result<(), i
 pub struct Args {
     pub src_dir: PathBuf,
     pub dst_dir: PathBuf,
+    /// Glob patterns (`--exclude`, repeatable) naming paths to leave out of the sync.
+    pub excludes: Vec<String>,
+    /// Glob patterns (`--include`, repeatable) that override a matching `--exclude`.
+    pub includes: Vec<String>,
+    /// `--dry-run`: compute the sync plan but don't touch the filesystem, printing a preview.
+    pub dry_run: bool,
+    /// `--json`: print the `--dry-run` preview as machine-readable JSON instead of plain text.
+    pub json: bool,
 }
 
 impl Args {
     pub fn new() -> Args {
         let args: Vec<String> = env::args().skip(1).collect();
-
-        if args.len() != 2 {
+        Self::parse(args).unwrap_or_else(|message| {
             eprintln!("{} - rsync for two directories", "rs-ync".green());
-            eprintln!("Usage: rs-ync `<SRC>` `<DST>`");
             eprintln!(
-                "{} wrong number of args: expected 2 got {}. ",
-                "Error:".bold().red(),
-                args.len()
+                "Usage: rs-ync `<SRC>` `<DST>` [--exclude <glob>]... [--include <glob>]... [--dry-run] [--json]"
             );
+            eprintln!("{} {}", "Error:".bold().red(), message);
             std::process::exit(1);
+        })
+    }
+
+    fn parse(args: Vec<String>) -> Result<Args, String> {
+        let mut positional = Vec::new();
+        let mut excludes = Vec::new();
+        let mut includes = Vec::new();
+        let mut dry_run = false;
+        let mut json = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--exclude" => excludes.push(
+                    args.next()
+                        .ok_or_else(|| "--exclude requires a glob argument".to_string())?,
+                ),
+                "--include" => includes.push(
+                    args.next()
+                        .ok_or_else(|| "--include requires a glob argument".to_string())?,
+                ),
+                "--dry-run" => dry_run = true,
+                "--json" => json = true,
+                _ => positional.push(arg),
+            }
         }
-        Args {
-            src_dir: PathBuf::from(args[0].clone()),
-            dst_dir: PathBuf::from(args[1].clone()),
+
+        if positional.len() != 2 {
+            return Err(format!(
+                "wrong number of args: expected 2 got {}. ",
+                positional.len()
+            ));
         }
+
+        Ok(Args {
+            src_dir: PathBuf::from(&positional[0]),
+            dst_dir: PathBuf::from(&positional[1]),
+            excludes,
+            includes,
+            dry_run,
+            json,
+        })
     }
 }
 
@@ -340,8 +1326,8 @@ impl fmt::Display for Args {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "src_dir: {:?} | dst_dir: {:?}",
-            self.src_dir, self.dst_dir
+            "src_dir: {:?} | dst_dir: {:?} | excludes: {:?} | includes: {:?} | dry_run: {} | json: {}",
+            self.src_dir, self.dst_dir, self.excludes, self.includes, self.dry_run, self.json
         )
     }
 }
@@ -1,4 +1,4 @@
-use hash_files::FakeFileSystem;
+use rs_ync::FakeFileSystem;
 
 pub fn setup_fake_fs() -> FakeFileSystem {
     let mut file_sys = FakeFileSystem::new();
@@ -19,3 +19,33 @@ pub fn setup_fake_fs() -> FakeFileSystem {
     }
     file_sys
 }
+
+pub fn setup_renamed_fake_fs() -> FakeFileSystem {
+    let mut file_sys = FakeFileSystem::new();
+
+    let values = vec![
+        ("dir/renamed.txt", "hello"),
+        ("other_dir/original.txt", "hello"),
+    ];
+
+    for (name, content) in values {
+        file_sys.files.insert(name.into(), content.to_string());
+    }
+    file_sys
+}
+
+pub fn setup_nested_fake_fs() -> FakeFileSystem {
+    let mut file_sys = FakeFileSystem::new();
+
+    let values = vec![
+        ("dir/file_1.txt", "hey"),
+        ("dir/sub/file_2.txt", "hello"),
+        ("dir/sub/deeper/file_3.txt", "world"),
+        ("other_dir/file_1.txt", "hey"),
+    ];
+
+    for (name, content) in values {
+        file_sys.files.insert(name.into(), content.to_string());
+    }
+    file_sys
+}
@@ -1,4 +1,4 @@
-use rs_ync::{Args, execute_rsync};
+use rs_ync::{Args, FakeFileSystem, execute_rsync, plan_rsync};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -11,6 +11,10 @@ fn test_edge_to_edge() {
     let args = Args {
         src_dir: PathBuf::from("dir"),
         dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
     };
     let _ = execute_rsync(args, &mut file_sys);
 
@@ -31,18 +35,323 @@ fn test_edge_to_edge() {
         (PathBuf::from("other_dir/file_2.txt"), String::from("hello")),
     ]);
 
+    // The hash cache persisted alongside the synced files isn't part of the tree being synced.
+    file_sys
+        .files
+        .remove(&PathBuf::from("other_dir/.rs-ync-cache"));
     assert_eq!(file_sys.files, expected_result);
 
     let expected_ops = vec![
         "list: `dir`",
         "list: `other_dir`",
+        "mkdir: `other_dir`",
         "copy: `dir/file_1.txt` -> `other_dir/file_1.txt`",
+        "mkdir: `other_dir`",
         "copy: `dir/file_3.txt` -> `other_dir/file_3.txt`",
+        "mkdir: `other_dir`",
         "copy: `dir/some_file.rs` -> `other_dir/some_file.rs`",
         "delete: `other_dir/file4.rs`",
         "delete: `other_dir/file_5.txt`",
         "delete: `other_dir/file_7.txt`",
+        "write: `other_dir/.rs-ync-cache`",
     ];
 
     assert_eq!(file_sys.operations, expected_ops);
 }
+
+#[test]
+fn test_rename_is_moved_not_recopied() {
+    let mut file_sys = common::setup_renamed_fake_fs();
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    let expected_result = HashMap::from([
+        (PathBuf::from("dir/renamed.txt"), String::from("hello")),
+        (
+            PathBuf::from("other_dir/renamed.txt"),
+            String::from("hello"),
+        ),
+    ]);
+
+    file_sys
+        .files
+        .remove(&PathBuf::from("other_dir/.rs-ync-cache"));
+    assert_eq!(file_sys.files, expected_result);
+    assert_eq!(
+        file_sys.operations,
+        vec![
+            "list: `dir`",
+            "list: `other_dir`",
+            "mkdir: `other_dir`",
+            "move: `other_dir/original.txt` -> `other_dir/renamed.txt`",
+            "write: `other_dir/.rs-ync-cache`",
+        ]
+    );
+}
+
+#[test]
+fn test_patch_transfers_only_changed_blocks() {
+    let mut file_sys = common::setup_fake_fs();
+
+    let block_a = "A".repeat(4096);
+    let block_b = "B".repeat(4096);
+    let block_c = "C".repeat(100);
+    let block_x = "X".repeat(4096);
+
+    let dst_content = format!("{block_a}{block_b}{block_c}");
+    let src_content = format!("{block_a}{block_x}{block_c}");
+
+    file_sys
+        .files
+        .insert(PathBuf::from("dir/big.txt"), src_content.clone());
+    file_sys
+        .files
+        .insert(PathBuf::from("other_dir/big.txt"), dst_content);
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    assert_eq!(
+        file_sys.files.get(&PathBuf::from("other_dir/big.txt")),
+        Some(&src_content)
+    );
+    assert!(
+        file_sys
+            .operations
+            .contains(&"write: `other_dir/big.txt`".to_string())
+    );
+    assert!(
+        !file_sys
+            .operations
+            .contains(&"copy: `dir/big.txt` -> `other_dir/big.txt`".to_string())
+    );
+}
+
+#[test]
+fn test_recursive_sync_preserves_tree_structure() {
+    let mut file_sys = common::setup_nested_fake_fs();
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    let expected_result = HashMap::from([
+        (PathBuf::from("dir/file_1.txt"), String::from("hey")),
+        (PathBuf::from("dir/sub/file_2.txt"), String::from("hello")),
+        (
+            PathBuf::from("dir/sub/deeper/file_3.txt"),
+            String::from("world"),
+        ),
+        (PathBuf::from("other_dir/file_1.txt"), String::from("hey")),
+        (
+            PathBuf::from("other_dir/sub/file_2.txt"),
+            String::from("hello"),
+        ),
+        (
+            PathBuf::from("other_dir/sub/deeper/file_3.txt"),
+            String::from("world"),
+        ),
+    ]);
+
+    file_sys
+        .files
+        .remove(&PathBuf::from("other_dir/.rs-ync-cache"));
+    assert_eq!(file_sys.files, expected_result);
+}
+
+#[test]
+fn test_second_sync_reuses_hash_cache_and_is_a_no_op() {
+    // Both sides already hold identical files, so every sync here is a pure content comparison -
+    // no copy/move/delete ever touches a file's mtime, isolating the hash cache as the only
+    // thing that could change between the two runs.
+    let mut file_sys = FakeFileSystem::new();
+    file_sys.files.insert("dir/file_1.txt".into(), "hey".to_string());
+    file_sys
+        .files
+        .insert("other_dir/file_1.txt".into(), "hey".to_string());
+    file_sys.files.insert("dir/file_2.txt".into(), "hello".to_string());
+    file_sys
+        .files
+        .insert("other_dir/file_2.txt".into(), "hello".to_string());
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    assert!(
+        file_sys
+            .files
+            .contains_key(&PathBuf::from("other_dir/.rs-ync-cache"))
+    );
+    // The first sync had an empty cache to work with, so it actually had to hash files to
+    // compare same-sized candidates.
+    assert!(file_sys.hashes_computed.get() > 0);
+
+    file_sys.operations.clear();
+    file_sys.hashes_computed.set(0);
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    assert_eq!(
+        file_sys.operations,
+        vec![
+            "list: `dir`",
+            "list: `other_dir`",
+            "write: `other_dir/.rs-ync-cache`",
+        ]
+    );
+    // Every size-collision comparison on the second run should be served from the persisted
+    // cache - none of them should need to actually re-hash a file's content.
+    assert_eq!(file_sys.hashes_computed.get(), 0);
+}
+
+#[test]
+fn test_exclude_pattern_leaves_matching_files_untouched_on_both_sides() {
+    let mut file_sys = common::setup_fake_fs();
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec!["*.rs".to_string()],
+        includes: vec![],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    // Excluded on the source side: never copied.
+    assert!(
+        !file_sys
+            .files
+            .contains_key(&PathBuf::from("other_dir/some_file.rs"))
+    );
+    assert_eq!(
+        file_sys.files.get(&PathBuf::from("dir/some_file.rs")),
+        Some(&"let mut thing = Vec::new()".to_string())
+    );
+
+    // Excluded on the destination side too: not flagged for deletion even though it has no
+    // counterpart in the source tree.
+    assert_eq!(
+        file_sys.files.get(&PathBuf::from("other_dir/file4.rs")),
+        Some(&"let x = 4;".to_string())
+    );
+
+    // Non-excluded files still sync normally.
+    assert_eq!(
+        file_sys.files.get(&PathBuf::from("other_dir/file_1.txt")),
+        Some(&"hey".to_string())
+    );
+    assert!(
+        !file_sys
+            .files
+            .contains_key(&PathBuf::from("other_dir/file_5.txt"))
+    );
+}
+
+#[test]
+fn test_rsyncignore_file_is_overridden_by_explicit_include() {
+    let mut file_sys = common::setup_fake_fs();
+    file_sys
+        .files
+        .insert(PathBuf::from("dir/.rsyncignore"), "*.rs\n".to_string());
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec!["some_file.rs".to_string()],
+        dry_run: false,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    // `.rsyncignore` excludes every `.rs` file, but the explicit `--include` overrides it.
+    assert_eq!(
+        file_sys.files.get(&PathBuf::from("other_dir/some_file.rs")),
+        Some(&"let mut thing = Vec::new()".to_string())
+    );
+}
+
+#[test]
+fn test_dry_run_plans_without_touching_the_filesystem() {
+    let mut file_sys = common::setup_fake_fs();
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: true,
+        json: false,
+    };
+    let _ = execute_rsync(args, &mut file_sys);
+
+    // Only the read-only listing happened - no copy/move/patch/delete/write.
+    assert_eq!(
+        file_sys.operations,
+        vec!["list: `dir`", "list: `other_dir`"]
+    );
+    assert!(
+        !file_sys
+            .files
+            .contains_key(&PathBuf::from("other_dir/.rs-ync-cache"))
+    );
+}
+
+#[test]
+fn test_plan_rsync_reports_planned_actions() {
+    let mut file_sys = common::setup_fake_fs();
+
+    let args = Args {
+        src_dir: PathBuf::from("dir"),
+        dst_dir: PathBuf::from("other_dir"),
+        excludes: vec![],
+        includes: vec![],
+        dry_run: true,
+        json: false,
+    };
+    let summary = plan_rsync(&args, &mut file_sys);
+
+    assert_eq!(summary.copies, 3);
+    assert_eq!(summary.moves, 0);
+    assert_eq!(summary.patches, 0);
+    assert_eq!(summary.deletes, 3);
+    assert_eq!(summary.actions.len(), 6);
+    assert!(summary.to_json().contains("\"copies\":3"));
+}